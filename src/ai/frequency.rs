@@ -0,0 +1,123 @@
+//! A lighter, counting-based alternative to [`crate::ai::IsmctsAi`]: instead
+//! of searching, it reads the [`ComputerBrain`] tendencies `GameState` has
+//! accumulated across the session and picks whichever action best exploits
+//! them.
+
+use crate::betting::Stake;
+use crate::brain::ComputerBrain;
+use crate::card::{Card, CardList, Rank};
+use crate::engine::{Action, GameState};
+use core::cmp::Ordering;
+
+#[derive(Default)]
+pub struct FrequencyAi;
+
+impl FrequencyAi {
+    pub fn new() -> FrequencyAi {
+        FrequencyAi
+    }
+
+    pub fn choose_action(&self, game: &GameState) -> Action {
+        let legal = game.legal_actions();
+        let manilha_rank = game.manilha_rank();
+        let hand = game.current_hand();
+
+        if legal.contains(&Action::Accept) {
+            return self.choose_response(game, manilha_rank, hand, &legal);
+        }
+
+        if legal.contains(&Action::CallTruco)
+            && game.bet().current_stake() == Stake::Parada
+            && self.should_open(game.brain(), hand, manilha_rank)
+        {
+            return Action::CallTruco;
+        }
+
+        Action::PlayCard(self.choose_card(game, hand, manilha_rank))
+    }
+
+    /// We're being asked to Accept/raise/Fold the player's call. A manilha
+    /// is always worth seeing through; otherwise, push back only against a
+    /// player who bluffs more often than not, and fold to anyone else.
+    fn choose_response(
+        &self,
+        game: &GameState,
+        manilha_rank: Option<Rank>,
+        hand: &CardList,
+        legal: &[Action],
+    ) -> Action {
+        if has_manilha(hand, manilha_rank) {
+            return Action::Accept;
+        }
+
+        if game.brain().bluff_rate() > 0.5 {
+            if legal.contains(&Action::CallTruco) {
+                return Action::CallTruco;
+            }
+            return Action::Accept;
+        }
+
+        Action::Fold
+    }
+
+    /// Whether to open with a call on a hand that doesn't (yet) have the
+    /// stake raised: worth it with a manilha, and worth bluffing when the
+    /// player folds to pressure often enough that the bluff usually works.
+    fn should_open(&self, brain: &ComputerBrain, hand: &CardList, manilha_rank: Option<Rank>) -> bool {
+        has_manilha(hand, manilha_rank) || brain.fold_to_reraise_rate() > 0.5
+    }
+
+    fn choose_card(&self, game: &GameState, hand: &CardList, manilha_rank: Option<Rank>) -> usize {
+        let leading = game.tricks_played().is_multiple_of(2);
+
+        if !leading {
+            if let Some(to_beat) = game.turn_stack().last().copied() {
+                if let Some(index) = winning_index(hand, to_beat, manilha_rank) {
+                    return index;
+                }
+            }
+            return weakest_index(hand, manilha_rank);
+        }
+
+        // Against a player who rarely folds under pressure, showing the
+        // manilha early just gives away the hand's one real threat for
+        // nothing; hold it back and lead the weakest card instead.
+        if has_manilha(hand, manilha_rank) && game.brain().fold_to_reraise_rate() < 0.5 {
+            return weakest_non_manilha_index(hand, manilha_rank)
+                .unwrap_or_else(|| weakest_index(hand, manilha_rank));
+        }
+
+        weakest_index(hand, manilha_rank)
+    }
+}
+
+fn has_manilha(hand: &CardList, manilha_rank: Option<Rank>) -> bool {
+    hand.0.iter().any(|card| card.is_manilha(manilha_rank))
+}
+
+fn winning_index(hand: &CardList, to_beat: Card, manilha_rank: Option<Rank>) -> Option<usize> {
+    hand.0
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| card.trick_cmp(to_beat, manilha_rank) == Ordering::Greater)
+        .min_by(|(_, a), (_, b)| a.trick_cmp(**b, manilha_rank))
+        .map(|(i, _)| i)
+}
+
+fn weakest_index(hand: &CardList, manilha_rank: Option<Rank>) -> usize {
+    hand.0
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.trick_cmp(**b, manilha_rank))
+        .map(|(i, _)| i)
+        .expect("hand is not empty")
+}
+
+fn weakest_non_manilha_index(hand: &CardList, manilha_rank: Option<Rank>) -> Option<usize> {
+    hand.0
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| !card.is_manilha(manilha_rank))
+        .min_by(|(_, a), (_, b)| a.trick_cmp(**b, manilha_rank))
+        .map(|(i, _)| i)
+}
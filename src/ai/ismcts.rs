@@ -0,0 +1,231 @@
+//! Information-Set Monte Carlo Tree Search opponent.
+//!
+//! Truco hides each side's hand from the other, so a plain MCTS tree can't
+//! be searched directly. Instead, each iteration *determinizes* the hidden
+//! information (the player's hand plus the remaining deck) into one
+//! concrete, fully-observable world, then runs a normal
+//! selection/expansion/rollout/backpropagation pass over it with UCB1. The
+//! same tree, keyed by the sequence of `Action`s taken from the root, is
+//! reused across determinizations: that's what makes the nodes
+//! "information sets" rather than per-world nodes. After many iterations,
+//! the root's most-visited action is the move played.
+
+use crate::card::{CardList, Rank, Turn};
+use crate::engine::{Action, GameState, Outcome};
+use core::cmp::Ordering;
+use std::collections::HashMap;
+
+const DEFAULT_ITERATIONS: usize = 300;
+const EXPLORATION: f64 = core::f64::consts::SQRT_2;
+
+#[derive(Default)]
+struct ActionStats {
+    visits: u32,
+    total_reward: f64,
+}
+
+#[derive(Default)]
+struct NodeStats {
+    visits: u32,
+    actions: HashMap<Action, ActionStats>,
+}
+
+impl NodeStats {
+    fn new(legal: &[Action]) -> NodeStats {
+        NodeStats {
+            visits: 0,
+            actions: legal.iter().map(|&a| (a, ActionStats::default())).collect(),
+        }
+    }
+}
+
+pub struct IsmctsAi {
+    iterations: usize,
+    perspective: Turn,
+    tree: HashMap<Vec<Action>, NodeStats>,
+}
+
+impl IsmctsAi {
+    pub fn new(iterations: usize) -> IsmctsAi {
+        IsmctsAi {
+            iterations,
+            perspective: Turn::Computer,
+            tree: HashMap::new(),
+        }
+    }
+
+    /// Runs the search from `state` (whoever's turn it is) and returns the
+    /// most-visited legal action.
+    pub fn choose_action(&mut self, state: &GameState) -> Action {
+        self.perspective = state.turn();
+
+        let legal = state.legal_actions();
+        if legal.len() <= 1 {
+            return legal.into_iter().next().expect("some action must be legal");
+        }
+
+        self.tree.clear();
+
+        for _ in 0..self.iterations {
+            let determinized = state.determinize(self.perspective);
+            self.iterate(determinized, Vec::new());
+        }
+
+        let root = self.tree.get(&Vec::new()).expect("root was visited");
+
+        legal
+            .into_iter()
+            .max_by_key(|action| root.actions.get(action).map_or(0, |s| s.visits))
+            .expect("some action must be legal")
+    }
+
+    /// One selection/expansion/rollout/backpropagation pass, returning the
+    /// reward (from `self.perspective`'s point of view) propagated back up.
+    fn iterate(&mut self, mut state: GameState, path: Vec<Action>) -> f64 {
+        let legal = state.legal_actions();
+        if legal.is_empty() {
+            return 0.0;
+        }
+
+        self.tree
+            .entry(path.clone())
+            .or_insert_with(|| NodeStats::new(&legal));
+
+        let action = self.select(&path, &legal, state.turn());
+        let outcomes = state.apply(action).unwrap_or_default();
+        let winner = hand_winner(&outcomes);
+
+        let mut child_path = path.clone();
+        child_path.push(action);
+
+        let reward = match winner {
+            Some(winner) => self.reward_for(winner),
+            None if self.tree.contains_key(&child_path) => self.iterate(state, child_path.clone()),
+            None => {
+                let next_legal = state.legal_actions();
+                self.tree
+                    .entry(child_path.clone())
+                    .or_insert_with(|| NodeStats::new(&next_legal));
+                self.rollout(state)
+            }
+        };
+
+        let node = self.tree.get_mut(&path).expect("inserted above");
+        node.visits += 1;
+
+        let stats = node.actions.get_mut(&action).expect("action was legal");
+        stats.visits += 1;
+        stats.total_reward += reward;
+
+        reward
+    }
+
+    /// Picks the child with the highest UCB1 score, favoring unvisited
+    /// actions first so every legal move gets tried at least once. Rewards
+    /// are tracked from `self.perspective`'s point of view, so at a node
+    /// where the opponent is choosing, their best move is the one that
+    /// *minimizes* that reward, not maximizes it.
+    fn select(&self, path: &[Action], legal: &[Action], turn: Turn) -> Action {
+        let node = self.tree.get(path).expect("node exists");
+        let parent_visits = node.visits.max(1) as f64;
+        let sign = if turn == self.perspective { 1.0 } else { -1.0 };
+
+        *legal
+            .iter()
+            .max_by(|&&a, &&b| {
+                ucb1(node.actions.get(&a), parent_visits, sign)
+                    .partial_cmp(&ucb1(node.actions.get(&b), parent_visits, sign))
+                    .unwrap_or(Ordering::Equal)
+            })
+            .expect("some action must be legal")
+    }
+
+    /// Plays out the determinized state to the end of the hand using a
+    /// cheap heuristic: play the lowest card that still wins the trick, or
+    /// else discard the weakest card in hand.
+    fn rollout(&self, mut state: GameState) -> f64 {
+        loop {
+            let legal = state.legal_actions();
+            if legal.is_empty() {
+                return 0.0;
+            }
+
+            let action = rollout_action(&state, &legal);
+            let outcomes = state.apply(action).unwrap_or_default();
+
+            if let Some(winner) = hand_winner(&outcomes) {
+                return self.reward_for(winner);
+            }
+        }
+    }
+
+    fn reward_for(&self, winner: Turn) -> f64 {
+        if winner == self.perspective {
+            1.0
+        } else {
+            -1.0
+        }
+    }
+}
+
+impl Default for IsmctsAi {
+    fn default() -> Self {
+        IsmctsAi::new(DEFAULT_ITERATIONS)
+    }
+}
+
+fn ucb1(stats: Option<&ActionStats>, parent_visits: f64, sign: f64) -> f64 {
+    match stats {
+        Some(stats) if stats.visits > 0 => {
+            let exploit = sign * stats.total_reward / stats.visits as f64;
+            let explore = EXPLORATION * (parent_visits.ln() / stats.visits as f64).sqrt();
+            exploit + explore
+        }
+        _ => f64::INFINITY,
+    }
+}
+
+fn hand_winner(outcomes: &[Outcome]) -> Option<Turn> {
+    outcomes.iter().find_map(|outcome| match outcome {
+        Outcome::HandWon { winner, .. }
+        | Outcome::BetFolded { winner, .. }
+        | Outcome::GameWon { winner } => Some(*winner),
+        _ => None,
+    })
+}
+
+fn rollout_action(state: &GameState, legal: &[Action]) -> Action {
+    if legal.contains(&Action::Accept) {
+        return Action::Accept;
+    }
+
+    let manilha_rank = state.manilha_rank();
+    let hand = state.hand_of(state.turn());
+    let leading = state.tricks_played().is_multiple_of(2);
+    let to_beat = if leading {
+        None
+    } else {
+        state.turn_stack().last().copied()
+    };
+
+    let index = match to_beat {
+        Some(to_beat) => hand
+            .iter()
+            .enumerate()
+            .filter(|(_, card)| card.trick_cmp(to_beat, manilha_rank) == Ordering::Greater)
+            .min_by(|(_, a), (_, b)| a.trick_cmp(**b, manilha_rank))
+            .map(|(i, _)| i)
+            .unwrap_or_else(|| weakest_index(hand, manilha_rank)),
+        None => weakest_index(hand, manilha_rank),
+    };
+
+    Action::PlayCard(index)
+}
+
+fn weakest_index(hand: &CardList, manilha_rank: Option<Rank>) -> usize {
+    hand.iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| a.trick_cmp(**b, manilha_rank))
+        .map(|(i, _)| i)
+        .expect("hand is not empty")
+}
@@ -0,0 +1,27 @@
+//! Opponent AIs. Each implements the same shape: given a read-only
+//! `GameState`, pick one of its `legal_actions()`.
+
+pub mod frequency;
+pub mod ismcts;
+
+pub use frequency::FrequencyAi;
+pub use ismcts::IsmctsAi;
+
+use crate::engine::{Action, GameState};
+
+/// The computer opponent driving play, picked once at startup.
+pub enum Opponent {
+    /// Cheap and reactive: reads tendencies tracked across the session.
+    Frequency(FrequencyAi),
+    /// Slower and stronger: searches a determinized game tree each turn.
+    Ismcts(IsmctsAi),
+}
+
+impl Opponent {
+    pub fn choose_action(&mut self, game: &GameState) -> Action {
+        match self {
+            Opponent::Frequency(ai) => ai.choose_action(game),
+            Opponent::Ismcts(ai) => ai.choose_action(game),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use crate::card::Turn;
+
+/// The stake ladder a hand can be raised along: parada -> truco -> seis -> nove -> doze.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Stake {
+    Parada = 1,
+    Truco = 3,
+    Seis = 6,
+    Nove = 9,
+    Doze = 12,
+}
+
+impl Stake {
+    fn next(self) -> Option<Stake> {
+        match self {
+            Stake::Parada => Some(Stake::Truco),
+            Stake::Truco => Some(Stake::Seis),
+            Stake::Seis => Some(Stake::Nove),
+            Stake::Nove => Some(Stake::Doze),
+            Stake::Doze => None,
+        }
+    }
+
+    pub fn points(self) -> u8 {
+        self as u8
+    }
+}
+
+impl std::fmt::Display for Stake {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Stake::Parada => "parada",
+                Stake::Truco => "truco",
+                Stake::Seis => "seis",
+                Stake::Nove => "nove",
+                Stake::Doze => "doze",
+            }
+        )
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum BetError {
+    NoRaisePending,
+    AlreadyMaxed,
+    NotYourTurn,
+}
+
+impl std::fmt::Display for BetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                BetError::NoRaisePending => "não há pedido de aumento em aberto",
+                BetError::AlreadyMaxed => "a aposta já está no máximo (doze)",
+                BetError::NotYourTurn => "não é sua vez de responder",
+            }
+        )
+    }
+}
+
+/// Tracks the current stake of a hand and who owes a response to the last raise.
+#[derive(Clone)]
+pub struct BetState {
+    stake: Stake,
+    accepted_stake: Stake,
+    last_raiser: Option<Turn>,
+    awaiting_response: Option<Turn>,
+}
+
+impl BetState {
+    pub fn new() -> BetState {
+        BetState {
+            stake: Stake::Parada,
+            accepted_stake: Stake::Parada,
+            last_raiser: None,
+            awaiting_response: None,
+        }
+    }
+
+    pub fn current_stake(&self) -> Stake {
+        self.stake
+    }
+
+    pub fn accepted_stake(&self) -> Stake {
+        self.accepted_stake
+    }
+
+    /// Who must accept, raise or fold the last call, if anyone.
+    pub fn responder(&self) -> Option<Turn> {
+        self.awaiting_response
+    }
+
+    pub fn last_raiser(&self) -> Option<Turn> {
+        self.last_raiser
+    }
+
+    /// Raises the stake to the next rung of the ladder (truco/seis/nove/doze).
+    /// The one who must respond to the last raise is allowed to raise again
+    /// instead of accepting or folding; anyone else is out of turn. A
+    /// re-raise implicitly accepts the stake it's raising past, same as a
+    /// real truco table: folding afterwards still awards that stake.
+    pub fn raise(&mut self, caller: Turn) -> Result<Stake, BetError> {
+        match self.awaiting_response {
+            Some(turn) if turn != caller => return Err(BetError::NotYourTurn),
+            Some(_) => self.accepted_stake = self.stake,
+            None => {}
+        }
+
+        let next = self.stake.next().ok_or(BetError::AlreadyMaxed)?;
+
+        self.stake = next;
+        self.last_raiser = Some(caller);
+        self.awaiting_response = Some(caller.opponent());
+
+        Ok(next)
+    }
+
+    pub fn call_truco(&mut self, caller: Turn) -> Result<Stake, BetError> {
+        self.raise(caller)
+    }
+
+    /// Accepts the pending raise, locking in its stake as the accepted one.
+    pub fn accept(&mut self, responder: Turn) -> Result<Stake, BetError> {
+        match self.awaiting_response {
+            Some(turn) if turn == responder => {
+                self.accepted_stake = self.stake;
+                self.awaiting_response = None;
+                Ok(self.accepted_stake)
+            }
+            Some(_) => Err(BetError::NotYourTurn),
+            None => Err(BetError::NoRaisePending),
+        }
+    }
+
+    /// Folds the pending raise. The hand is over; the caller of the raise wins
+    /// the stake that was accepted *before* this raise.
+    pub fn fold(&mut self, responder: Turn) -> Result<(Turn, Stake), BetError> {
+        match self.awaiting_response {
+            Some(turn) if turn == responder => {
+                self.awaiting_response = None;
+                Ok((responder.opponent(), self.accepted_stake))
+            }
+            Some(_) => Err(BetError::NotYourTurn),
+            None => Err(BetError::NoRaisePending),
+        }
+    }
+
+    pub fn reset(&mut self) {
+        *self = BetState::new();
+    }
+}
+
+impl Default for BetState {
+    fn default() -> Self {
+        BetState::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn calling_truco_raises_the_stake_and_awaits_the_opponent() {
+        let mut bet = BetState::new();
+        assert_eq!(bet.call_truco(Turn::Player), Ok(Stake::Truco));
+        assert_eq!(bet.current_stake(), Stake::Truco);
+        assert_eq!(bet.responder(), Some(Turn::Computer));
+    }
+
+    #[test]
+    fn the_caller_cannot_raise_again_before_the_opponent_responds() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap();
+        assert_eq!(bet.raise(Turn::Player), Err(BetError::NotYourTurn));
+    }
+
+    #[test]
+    fn the_awaited_responder_can_raise_again_instead_of_accepting() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap();
+        assert_eq!(bet.raise(Turn::Computer), Ok(Stake::Seis));
+        assert_eq!(bet.responder(), Some(Turn::Player));
+    }
+
+    #[test]
+    fn raising_past_doze_is_rejected() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap(); // truco
+        bet.raise(Turn::Computer).unwrap(); // seis
+        bet.raise(Turn::Player).unwrap(); // nove
+        bet.raise(Turn::Computer).unwrap(); // doze
+        assert_eq!(bet.raise(Turn::Player), Err(BetError::AlreadyMaxed));
+    }
+
+    #[test]
+    fn accepting_locks_in_the_raised_stake() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap();
+        assert_eq!(bet.accept(Turn::Computer), Ok(Stake::Truco));
+        assert_eq!(bet.accepted_stake(), Stake::Truco);
+        assert_eq!(bet.responder(), None);
+    }
+
+    #[test]
+    fn only_the_awaited_responder_can_accept_or_fold() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap();
+        assert_eq!(bet.accept(Turn::Player), Err(BetError::NotYourTurn));
+        assert_eq!(bet.fold(Turn::Player), Err(BetError::NotYourTurn));
+    }
+
+    #[test]
+    fn folding_awards_the_stake_accepted_before_the_raise_to_the_caller() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap(); // truco, awaiting computer
+        bet.raise(Turn::Computer).unwrap(); // seis, still unaccepted; locks in truco
+        assert_eq!(bet.fold(Turn::Player), Ok((Turn::Computer, Stake::Truco)));
+    }
+
+    #[test]
+    fn reset_restores_the_initial_state() {
+        let mut bet = BetState::new();
+        bet.call_truco(Turn::Player).unwrap();
+        bet.accept(Turn::Computer).unwrap();
+        bet.reset();
+        assert_eq!(bet.current_stake(), Stake::Parada);
+        assert_eq!(bet.accepted_stake(), Stake::Parada);
+        assert_eq!(bet.responder(), None);
+    }
+}
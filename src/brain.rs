@@ -0,0 +1,81 @@
+//! Session-long tendency tracking for the human player.
+//!
+//! This is deliberately not a search: it just keeps frequency counters of
+//! (situation -> player response), the same shape as a simple
+//! reinforcement-learning table for something like rock-paper-scissors,
+//! and exposes the observed rates so an AI can bias its decisions toward
+//! whatever the player actually tends to do rather than what's "correct".
+
+/// How many times out of how many opportunities a situation was observed.
+#[derive(Clone, Copy, Default)]
+struct Frequency {
+    hits: u32,
+    opportunities: u32,
+}
+
+impl Frequency {
+    fn observe(&mut self, happened: bool) {
+        self.opportunities += 1;
+
+        if happened {
+            self.hits += 1;
+        }
+    }
+
+    /// The observed rate, or `default` while there isn't enough data yet to
+    /// trust it over a neutral assumption.
+    fn rate(&self, default: f64) -> f64 {
+        const MIN_SAMPLES: u32 = 3;
+
+        if self.opportunities < MIN_SAMPLES {
+            default
+        } else {
+            f64::from(self.hits) / f64::from(self.opportunities)
+        }
+    }
+}
+
+/// Tracks the human player's tendencies across a session and reports the
+/// rates an opponent can exploit. Lives on `GameState` and, unlike the rest
+/// of a hand's state, is never reset by `deal_hand`.
+#[derive(Clone, Default)]
+pub struct ComputerBrain {
+    /// Raises the stake without holding a manilha.
+    bluffs_on_raise: Frequency,
+    /// Leads a trick with the strongest card in hand.
+    leads_with_strongest: Frequency,
+    /// Folds when facing a reraise (a call beyond the first "truco").
+    folds_to_reraise: Frequency,
+}
+
+impl ComputerBrain {
+    pub fn new() -> ComputerBrain {
+        ComputerBrain::default()
+    }
+
+    pub fn record_raise(&mut self, had_manilha: bool) {
+        self.bluffs_on_raise.observe(!had_manilha);
+    }
+
+    pub fn record_lead(&mut self, played_strongest: bool) {
+        self.leads_with_strongest.observe(played_strongest);
+    }
+
+    pub fn record_reraise_response(&mut self, folded: bool) {
+        self.folds_to_reraise.observe(folded);
+    }
+
+    /// How likely the player is bluffing the next time they raise, absent
+    /// stronger evidence either way.
+    pub fn bluff_rate(&self) -> f64 {
+        self.bluffs_on_raise.rate(0.3)
+    }
+
+    pub fn strongest_lead_rate(&self) -> f64 {
+        self.leads_with_strongest.rate(0.5)
+    }
+
+    pub fn fold_to_reraise_rate(&self) -> f64 {
+        self.folds_to_reraise.rate(0.3)
+    }
+}
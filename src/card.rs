@@ -0,0 +1,200 @@
+use core::cmp::Ordering;
+use core::fmt;
+use std::ops::{Deref, DerefMut};
+
+/// A truco rank, ordered weakest (`Three`) to strongest (`Four`) by its
+/// trick-taking strength on its own, ignoring suit and manilha.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum Rank {
+    Three,
+    Two,
+    Ace,
+    Knight,
+    Joker,
+    Queen,
+    Seven,
+    Six,
+    Five,
+    Four,
+}
+
+impl Rank {
+    pub const ALL: [Rank; 10] = [
+        Rank::Three,
+        Rank::Two,
+        Rank::Ace,
+        Rank::Knight,
+        Rank::Joker,
+        Rank::Queen,
+        Rank::Seven,
+        Rank::Six,
+        Rank::Five,
+        Rank::Four,
+    ];
+
+    /// The next rank up from this one, or `None` for `Four` (the strongest
+    /// rank turns into no manilha, since there's nothing above it).
+    pub fn successor(self) -> Option<Rank> {
+        let index = Rank::ALL.iter().position(|&r| r == self)?;
+        Rank::ALL.get(index + 1).copied()
+    }
+}
+
+impl fmt::Display for Rank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Rank::Three => "3",
+                Rank::Two => "2",
+                Rank::Ace => "A",
+                Rank::Knight => "K",
+                Rank::Joker => "J",
+                Rank::Queen => "Q",
+                Rank::Seven => "7",
+                Rank::Six => "6",
+                Rank::Five => "5",
+                Rank::Four => "4",
+            }
+        )
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+pub enum Suit {
+    Clubs,    // zap, gato, zorro
+    Hearts,   // copa
+    Spades,   // espadilha
+    Diamonds, // Ouros, Mole
+}
+
+impl Suit {
+    pub const ALL: [Suit; 4] = [Suit::Clubs, Suit::Hearts, Suit::Spades, Suit::Diamonds];
+}
+
+impl fmt::Display for Suit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Suit::Clubs => "♣",
+                Suit::Hearts => "♥",
+                Suit::Spades => "♠",
+                Suit::Diamonds => "♦",
+            }
+        )
+    }
+}
+
+/// A playing card packed into a single byte: rank in the high bits, suit in
+/// the low 2 bits. Small enough to pass and compare by value, and it makes
+/// manilha checks and trick strength a couple of arithmetic ops instead of
+/// an enum match.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Card(u8);
+
+impl Card {
+    pub fn new(rank: Rank, suit: Suit) -> Card {
+        Card(((rank as u8) << 2) | suit as u8)
+    }
+
+    pub fn rank(self) -> Rank {
+        Rank::ALL[(self.0 >> 2) as usize]
+    }
+
+    pub fn suit(self) -> Suit {
+        Suit::ALL[(self.0 & 0b11) as usize]
+    }
+
+    /// Whether this card is the manilha, given which rank is the manilha
+    /// this hand (see [`crate::engine::GameState::manilha_rank`]) — or
+    /// `None` if the variant being played has no manilhas at all.
+    pub fn is_manilha(self, manilha_rank: Option<Rank>) -> bool {
+        Some(self.rank()) == manilha_rank
+    }
+
+    /// A branch-free trick-strength value: a manilha always outranks every
+    /// plain card and ties among manilhas are broken by suit; plain cards
+    /// rank by `Rank` alone, so two plain cards of the same rank power to
+    /// the same value, i.e. an "empate" (tie).
+    fn power(self, manilha_rank: Option<Rank>) -> u8 {
+        let is_manilha = u8::from(self.is_manilha(manilha_rank));
+        is_manilha * (16 + self.suit() as u8) + (1 - is_manilha) * (self.rank() as u8)
+    }
+
+    /// Compares the trick-taking strength of two cards given which rank
+    /// sets the manilha (see [`Card::is_manilha`]).
+    pub fn trick_cmp(self, other: Card, manilha_rank: Option<Rank>) -> Ordering {
+        self.power(manilha_rank).cmp(&other.power(manilha_rank))
+    }
+}
+
+impl fmt::Display for Card {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.rank(), self.suit())
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub enum Turn {
+    Player,
+    Computer,
+}
+
+impl Turn {
+    pub fn opponent(self) -> Turn {
+        match self {
+            Turn::Player => Turn::Computer,
+            Turn::Computer => Turn::Player,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CardList(pub Vec<Card>);
+
+impl CardList {
+    pub fn new() -> CardList {
+        CardList(vec![])
+    }
+}
+
+impl Default for CardList {
+    fn default() -> Self {
+        CardList::new()
+    }
+}
+
+impl Deref for CardList {
+    type Target = Vec<Card>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for CardList {
+    fn deref_mut(&mut self) -> &mut Vec<Card> {
+        &mut self.0
+    }
+}
+
+impl fmt::Display for CardList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.0
+                .iter()
+                .map(|c| format!("{}", c))
+                .collect::<Vec<_>>()
+                .join(" - ")
+        )
+    }
+}
+
+pub fn is_odd(n: u32) -> bool {
+    n % 2 == 1
+}
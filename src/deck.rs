@@ -0,0 +1,75 @@
+//! Picks a truco variant: which ranks make up the deck, and how the
+//! manilha is determined. Passed to [`crate::engine::GameState::with_deck_config`].
+
+use crate::card::Rank;
+
+/// How the manilha is chosen for a hand.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ManilhaRule {
+    /// The rank above whatever card is turned sets the manilha, the
+    /// traditional "manilha velha" rule.
+    TurnedCard,
+    /// A fixed rank is always the manilha ("baralho limpo"), regardless of
+    /// what gets turned.
+    Fixed(Rank),
+    /// No rank is ever a manilha.
+    None,
+}
+
+/// Which ranks are dealt into the deck.
+#[derive(Clone, Debug, PartialEq)]
+pub enum RankSelection {
+    /// The full 10-rank Spanish deck (baralho de 40).
+    All,
+    /// Only these ranks.
+    Include(Vec<Rank>),
+    /// Every rank except these.
+    Exclude(Vec<Rank>),
+}
+
+impl RankSelection {
+    fn allows(&self, rank: Rank) -> bool {
+        match self {
+            RankSelection::All => true,
+            RankSelection::Include(ranks) => ranks.contains(&rank),
+            RankSelection::Exclude(ranks) => !ranks.contains(&rank),
+        }
+    }
+}
+
+/// Which ranks go in the deck and how the manilha is set for the hand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DeckConfig {
+    pub ranks: RankSelection,
+    pub manilha: ManilhaRule,
+}
+
+impl DeckConfig {
+    /// The standard 40-card game this engine has always dealt: every rank,
+    /// manilha set by whatever card is turned.
+    pub fn standard() -> DeckConfig {
+        DeckConfig {
+            ranks: RankSelection::All,
+            manilha: ManilhaRule::TurnedCard,
+        }
+    }
+
+    /// "Baralho limpo": a fixed rank is always the manilha, so nothing
+    /// needs to be turned to set it.
+    pub fn baralho_limpo(fixed_manilha: Rank) -> DeckConfig {
+        DeckConfig {
+            ranks: RankSelection::All,
+            manilha: ManilhaRule::Fixed(fixed_manilha),
+        }
+    }
+
+    pub fn allows(&self, rank: Rank) -> bool {
+        self.ranks.allows(rank)
+    }
+}
+
+impl Default for DeckConfig {
+    fn default() -> DeckConfig {
+        DeckConfig::standard()
+    }
+}
@@ -0,0 +1,470 @@
+//! Headless truco rules engine.
+//!
+//! `GameState` is the single source of truth for a match: it validates and
+//! applies moves through [`GameState::apply`] and reports what is legal to
+//! play through [`GameState::legal_actions`]. It performs no I/O of its own,
+//! so any frontend (a CLI, a test, a search-based AI) can drive it by
+//! querying legal actions and applying the one it picked.
+
+use crate::betting::{BetError, BetState, Stake};
+use crate::brain::ComputerBrain;
+use crate::card::{is_odd, Card, CardList, Rank, Suit, Turn};
+use crate::deck::{DeckConfig, ManilhaRule};
+use crate::trick::{RoundResolver, TrickWinner};
+use core::cmp::Ordering;
+use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng};
+
+const POINTS_TO_WIN: u8 = 12;
+const CARDS_PER_HAND: usize = 3;
+
+/// A move a frontend can request of the engine.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    /// Play the card at this index in the current player's hand.
+    PlayCard(usize),
+    /// Call truco, or raise an existing call to the next rung of the ladder.
+    CallTruco,
+    /// Accept the pending raise.
+    Accept,
+    /// Fold the pending raise, conceding the previously accepted stake.
+    Fold,
+}
+
+/// What happened as the result of a successfully applied [`Action`].
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    CardPlayed { player: Turn, card: Card },
+    TrickResolved { trick: usize, winner: TrickWinner },
+    HandWon { winner: Turn, points: u8 },
+    BetRaised { caller: Turn, stake: Stake },
+    BetAccepted { stake: Stake },
+    BetFolded { winner: Turn, points: u8 },
+    GameWon { winner: Turn },
+}
+
+/// Why an [`Action`] was refused.
+#[derive(Debug, PartialEq)]
+pub enum RuleError {
+    NotYourTurn,
+    InvalidCardIndex,
+    Bet(BetError),
+    GameOver,
+}
+
+impl From<BetError> for RuleError {
+    fn from(err: BetError) -> Self {
+        RuleError::Bet(err)
+    }
+}
+
+impl std::fmt::Display for RuleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleError::NotYourTurn => write!(f, "não é a sua vez"),
+            RuleError::InvalidCardIndex => write!(f, "essa carta não está na mão"),
+            RuleError::Bet(err) => write!(f, "{}", err),
+            RuleError::GameOver => write!(f, "a partida já acabou"),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct GameState {
+    rng: ThreadRng,
+    deck_config: DeckConfig,
+    player_hand: CardList,
+    computer_hand: CardList,
+    turned_card: Option<Card>,
+    deck: CardList,
+    player_score: u8,
+    computer_score: u8,
+    turn: Turn,
+    mao: Turn,
+    turn_stack: CardList,
+    /// Whose turn it is to play a card once the current bet is settled, if
+    /// a call interrupted the card-play order. Reraises bounce `turn` back
+    /// and forth between caller and responder; this remembers who was
+    /// actually "up" before the interruption so it can be restored.
+    pending_turn: Option<Turn>,
+    bet: BetState,
+    resolver: RoundResolver,
+    tricks_played: u8,
+    winner: Option<Turn>,
+    brain: ComputerBrain,
+}
+
+impl GameState {
+    pub fn new() -> GameState {
+        GameState::with_deck_config(DeckConfig::default())
+    }
+
+    /// Starts a game dealt under a specific truco variant (which ranks are
+    /// in the deck, how the manilha is set). See [`DeckConfig`].
+    pub fn with_deck_config(deck_config: DeckConfig) -> GameState {
+        GameState {
+            rng: thread_rng(),
+            deck_config,
+            player_hand: CardList::new(),
+            computer_hand: CardList::new(),
+            turned_card: None,
+            deck: CardList::new(),
+            player_score: 0,
+            computer_score: 0,
+            turn: Turn::Player,
+            mao: Turn::Player,
+            turn_stack: CardList::new(),
+            pending_turn: None,
+            bet: BetState::new(),
+            resolver: RoundResolver::new(Turn::Player),
+            tricks_played: 0,
+            winner: None,
+            brain: ComputerBrain::new(),
+        }
+    }
+
+    pub fn init(&mut self) {
+        self.build_deck();
+    }
+
+    pub fn get_scores(&self) -> (u8, u8) {
+        (self.player_score, self.computer_score)
+    }
+
+    pub fn turn(&self) -> Turn {
+        self.turn
+    }
+
+    pub fn winner(&self) -> Option<Turn> {
+        self.winner
+    }
+
+    pub fn turned_card(&self) -> Option<Card> {
+        self.turned_card
+    }
+
+    /// The rank that is the manilha this hand under the configured
+    /// [`DeckConfig`], or `None` if this variant has no manilhas.
+    pub fn manilha_rank(&self) -> Option<Rank> {
+        match &self.deck_config.manilha {
+            ManilhaRule::TurnedCard => self.turned_card.and_then(|card| card.rank().successor()),
+            ManilhaRule::Fixed(rank) => Some(*rank),
+            ManilhaRule::None => None,
+        }
+    }
+
+    pub fn turn_stack(&self) -> &CardList {
+        &self.turn_stack
+    }
+
+    pub fn hand_of(&self, turn: Turn) -> &CardList {
+        match turn {
+            Turn::Player => &self.player_hand,
+            Turn::Computer => &self.computer_hand,
+        }
+    }
+
+    pub fn current_hand(&self) -> &CardList {
+        self.hand_of(self.turn)
+    }
+
+    pub fn bet(&self) -> &BetState {
+        &self.bet
+    }
+
+    /// Who is "mão" (the hand leader) for the current hand.
+    pub fn mao(&self) -> Turn {
+        self.mao
+    }
+
+    /// The trick-by-trick history of the hand in progress.
+    pub fn trick_history(&self) -> &[TrickWinner] {
+        self.resolver.history()
+    }
+
+    /// The player tendencies observed so far this session.
+    pub fn brain(&self) -> &ComputerBrain {
+        &self.brain
+    }
+
+    /// How many cards have been played in the hand so far. Even means
+    /// whoever's turn it is leads the next trick; odd means they're
+    /// responding to the card already on top of `turn_stack`.
+    pub fn tricks_played(&self) -> u8 {
+        self.tricks_played
+    }
+
+    /// Builds a fully-observable clone of this state from `perspective`'s
+    /// point of view: the opponent's hand and the remaining deck are
+    /// reshuffled into a random, but count-consistent, assignment of the
+    /// cards `perspective` cannot see. This "determinization" is how a
+    /// search-based AI samples a concrete hidden-information world to
+    /// search over.
+    pub fn determinize(&self, perspective: Turn) -> GameState {
+        let mut clone = self.clone();
+        let opponent = perspective.opponent();
+
+        let mut unknown: Vec<Card> = clone.deck.0.drain(..).collect();
+        let opponent_hand_len = clone.hand_of(opponent).len();
+
+        match opponent {
+            Turn::Player => unknown.append(&mut clone.player_hand.0),
+            Turn::Computer => unknown.append(&mut clone.computer_hand.0),
+        }
+
+        unknown.shuffle(&mut clone.rng);
+
+        let redealt: Vec<Card> = unknown.drain(..opponent_hand_len).collect();
+
+        match opponent {
+            Turn::Player => clone.player_hand = CardList(redealt),
+            Turn::Computer => clone.computer_hand = CardList(redealt),
+        }
+
+        clone.deck = CardList(unknown);
+        clone
+    }
+
+    fn build_deck(&mut self) {
+        self.deck = CardList::new();
+
+        for suit in Suit::ALL {
+            for rank in Rank::ALL {
+                if self.deck_config.allows(rank) {
+                    self.deck.push(Card::new(rank, suit));
+                }
+            }
+        }
+
+        self.deck.shuffle(&mut self.rng);
+    }
+
+    /// Deals a fresh hand: shuffles the deck, deals three cards to each side
+    /// and flips the `turned_card` that determines the manilha.
+    pub fn deal_hand(&mut self) {
+        self.build_deck();
+        self.player_hand = CardList::new();
+        self.computer_hand = CardList::new();
+        self.turn_stack = CardList::new();
+        self.tricks_played = 0;
+        self.pending_turn = None;
+        self.bet.reset();
+        self.turn = self.mao;
+        self.resolver = RoundResolver::new(self.mao);
+
+        for i in 0..(CARDS_PER_HAND as u32 * 2) {
+            let card = self.deck.pop().unwrap();
+            let receiver = if is_odd(i) { self.mao.opponent() } else { self.mao };
+
+            match receiver {
+                Turn::Player => self.player_hand.push(card),
+                Turn::Computer => self.computer_hand.push(card),
+            }
+        }
+
+        self.turned_card = self.deck.pop();
+    }
+
+    /// Which moves are legal for whoever's turn it currently is.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.winner.is_some() {
+            return vec![];
+        }
+
+        if self.bet.responder() == Some(self.turn) {
+            let mut actions = vec![Action::Accept];
+
+            if self.bet.current_stake() != Stake::Doze {
+                actions.push(Action::CallTruco);
+            }
+
+            actions.push(Action::Fold);
+
+            return actions;
+        }
+
+        let mut actions: Vec<Action> = (0..self.current_hand().len())
+            .map(Action::PlayCard)
+            .collect();
+
+        if self.bet.current_stake() != Stake::Doze {
+            actions.push(Action::CallTruco);
+        }
+
+        actions
+    }
+
+    pub fn apply(&mut self, action: Action) -> Result<Vec<Outcome>, RuleError> {
+        if self.winner.is_some() {
+            return Err(RuleError::GameOver);
+        }
+
+        match action {
+            Action::PlayCard(index) => self.apply_play_card(index),
+            Action::CallTruco => self.apply_call_truco(),
+            Action::Accept => self.apply_accept(),
+            Action::Fold => self.apply_fold(),
+        }
+    }
+
+    fn apply_play_card(&mut self, index: usize) -> Result<Vec<Outcome>, RuleError> {
+        if self.bet.responder().is_some() {
+            return Err(RuleError::NotYourTurn);
+        }
+
+        let leading = self.tricks_played.is_multiple_of(2);
+        let manilha_rank = self.manilha_rank();
+
+        let hand = match self.turn {
+            Turn::Player => &mut self.player_hand,
+            Turn::Computer => &mut self.computer_hand,
+        };
+
+        if index >= hand.len() {
+            return Err(RuleError::InvalidCardIndex);
+        }
+
+        if self.turn == Turn::Player && leading {
+            let played = hand.0[index];
+            let played_strongest = hand
+                .0
+                .iter()
+                .all(|card| card.trick_cmp(played, manilha_rank) != Ordering::Greater);
+            self.brain.record_lead(played_strongest);
+        }
+
+        let card = hand.swap_remove(index);
+        let player = self.turn;
+
+        self.turn_stack.push(card);
+        self.tricks_played += 1;
+        self.turn = self.turn.opponent();
+
+        let mut events = vec![Outcome::CardPlayed { player, card }];
+
+        if is_odd(self.tricks_played as u32 - 1) {
+            let winner = self.trick_winner();
+            let trick = self.resolver.history().len() + 1;
+
+            events.push(Outcome::TrickResolved { trick, winner });
+
+            // The winner of a trick leads the next one; a tie leaves the
+            // lead with whoever led the tied trick (already `self.turn`,
+            // since it has flipped back and forth an even number of times).
+            match winner {
+                TrickWinner::Player => self.turn = Turn::Player,
+                TrickWinner::Computer => self.turn = Turn::Computer,
+                TrickWinner::Tied => {}
+            }
+
+            if let Some(hand_winner) = self.resolver.record_trick(winner) {
+                events.push(self.resolve_hand(hand_winner));
+            }
+        }
+
+        Ok(events)
+    }
+
+    /// Compares the two cards of the trick that just completed and reports
+    /// who played the stronger one, from the perspective of whoever's turn
+    /// it is *now* (i.e. after the turn has already flipped to the next
+    /// player).
+    fn trick_winner(&self) -> TrickWinner {
+        let len = self.turn_stack.len();
+        let drawn_card = self.turn_stack.0[len - 1];
+        let last_drawn = self.turn_stack.0[len - 2];
+
+        match drawn_card.trick_cmp(last_drawn, self.manilha_rank()) {
+            Ordering::Equal => TrickWinner::Tied,
+            Ordering::Greater => self.turn.opponent().into(),
+            Ordering::Less => self.turn.into(),
+        }
+    }
+
+    fn resolve_hand(&mut self, winner: Turn) -> Outcome {
+        let stake = self.bet.accepted_stake();
+        self.award(winner, stake.points());
+
+        if self.winner == Some(winner) {
+            Outcome::GameWon { winner }
+        } else {
+            Outcome::HandWon {
+                winner,
+                points: stake.points(),
+            }
+        }
+    }
+
+    fn apply_call_truco(&mut self) -> Result<Vec<Outcome>, RuleError> {
+        let caller = self.turn;
+        let stake = self.bet.call_truco(caller)?;
+
+        if caller == Turn::Player {
+            let manilha_rank = self.manilha_rank();
+            let had_manilha = self.player_hand.0.iter().any(|card| card.is_manilha(manilha_rank));
+            self.brain.record_raise(had_manilha);
+        }
+
+        self.pending_turn.get_or_insert(caller);
+        self.turn = self.turn.opponent();
+
+        Ok(vec![Outcome::BetRaised { caller, stake }])
+    }
+
+    fn apply_accept(&mut self) -> Result<Vec<Outcome>, RuleError> {
+        let is_reraise = self.bet.current_stake() != Stake::Truco;
+        let stake = self.bet.accept(self.turn)?;
+
+        if self.turn == Turn::Player && is_reraise {
+            self.brain.record_reraise_response(false);
+        }
+
+        if let Some(resume) = self.pending_turn.take() {
+            self.turn = resume;
+        }
+
+        Ok(vec![Outcome::BetAccepted { stake }])
+    }
+
+    fn apply_fold(&mut self) -> Result<Vec<Outcome>, RuleError> {
+        let is_reraise = self.bet.current_stake() != Stake::Truco;
+        let folder = self.turn;
+        let (winner, stake) = self.bet.fold(self.turn)?;
+
+        if folder == Turn::Player && is_reraise {
+            self.brain.record_reraise_response(true);
+        }
+
+        self.pending_turn = None;
+        self.award(winner, stake.points());
+
+        if self.winner == Some(winner) {
+            return Ok(vec![Outcome::GameWon { winner }]);
+        }
+
+        Ok(vec![Outcome::BetFolded {
+            winner,
+            points: stake.points(),
+        }])
+    }
+
+    fn award(&mut self, winner: Turn, points: u8) {
+        match winner {
+            Turn::Player => self.player_score += points,
+            Turn::Computer => self.computer_score += points,
+        }
+
+        self.mao = self.mao.opponent();
+
+        if self.player_score >= POINTS_TO_WIN {
+            self.winner = Some(Turn::Player);
+        } else if self.computer_score >= POINTS_TO_WIN {
+            self.winner = Some(Turn::Computer);
+        }
+    }
+}
+
+impl Default for GameState {
+    fn default() -> Self {
+        GameState::new()
+    }
+}
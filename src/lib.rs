@@ -0,0 +1,10 @@
+//! Headless truco engine plus two opponent AIs. `main.rs` is a thin CLI
+//! frontend built on top of this library; start at [`engine::GameState`].
+
+pub mod ai;
+pub mod betting;
+pub mod brain;
+pub mod card;
+pub mod deck;
+pub mod engine;
+pub mod trick;
@@ -1,363 +1,171 @@
-use core::cmp::Ordering;
-use core::fmt;
-use rand::{rngs::ThreadRng, seq::SliceRandom, thread_rng};
-use std::ops::{Deref, DerefMut};
+use truco::ai::{FrequencyAi, IsmctsAi, Opponent};
+use truco::card::Turn;
+use truco::engine::{Action, GameState, Outcome};
+use truco::trick::TrickWinner;
 
-#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
-enum Suit {
-    Clubs,    // zap, gato, zorro
-    Hearts,   // copa
-    Spades,   // espadilha
-    Diamonds, // Ouros, Mole
-}
+fn main() {
+    println!("Bora jogar um truco?");
 
-impl fmt::Display for Suit {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Suit::Clubs => "♣",
-                Suit::Hearts => "♥",
-                Suit::Spades => "♠",
-                Suit::Diamonds => "♦",
-            }
-        )
-    }
+    run();
 }
 
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
-enum Card {
-    Three,
-    Two,
-    Ace,
-    Knight,
-    Joker,
-    Queen,
-    Seven,
-    Six,
-    Five,
-    Four,
-}
+/// Thin CLI frontend: renders the engine's state and outcomes, and turns
+/// stdin into `Action`s. All rules live in `engine::GameState`.
+fn run() {
+    println!("Iniciando...");
+    println!("Jogando contra o computador...");
 
-impl fmt::Display for Card {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            match self {
-                Card::Three => "3",
-                Card::Two => "2",
-                Card::Ace => "A",
-                Card::Knight => "K",
-                Card::Joker => "J",
-                Card::Queen => "Q",
-                Card::Seven => "7",
-                Card::Six => "6",
-                Card::Five => "5",
-                Card::Four => "4",
-            }
-        )
-    }
-}
+    let mut game = GameState::new();
+    game.init();
 
-#[derive(Debug, PartialEq)]
-struct CardWithSuit(Card, Suit);
+    let mut computer = choose_opponent();
 
-impl CardWithSuit {
-    fn is_manilha(&self, turned_card: &CardWithSuit) -> bool {
-        (turned_card.0 as u8 + 1) == self.0 as u8
-    }
-}
+    'game: loop {
+        let (ps, cs) = game.get_scores();
+        println!("Placar atual: Jogador: {} - Computador: {}", ps, cs);
+
+        game.deal_hand();
 
-impl PartialOrd for CardWithSuit {
-    fn partial_cmp(&self, other: &CardWithSuit) -> Option<Ordering> {
-        match self.0.partial_cmp(&other.0)? {
-            Ordering::Equal => self.1.partial_cmp(&other.1),
-            _ => Some(self.0.partial_cmp(&other.0)?),
+        println!("Sua mão: {}", game.hand_of(Turn::Player));
+
+        if let Some(carta_virada) = game.turned_card() {
+            println!("Carta virada: {}", carta_virada);
         }
-    }
-}
 
-impl fmt::Display for CardWithSuit {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}{}", self.0, self.1)
-    }
-}
+        loop {
+            println!("A cartas jogadas foram: {}", game.turn_stack());
 
-#[derive(Copy, Clone, PartialEq, PartialOrd)]
-enum Turn {
-    Player,
-    Computer,
-}
+            let action = choose_action(&game, &mut computer);
 
-struct CardList(Vec<CardWithSuit>);
+            let events = match game.apply(action) {
+                Ok(events) => events,
+                Err(err) => {
+                    println!("Jogada inválida: {}", err);
+                    continue;
+                }
+            };
 
-impl CardList {
-    fn new() -> CardList {
-        CardList(vec![])
-    }
-}
+            let mut hand_over = false;
 
-impl Deref for CardList {
-    type Target = Vec<CardWithSuit>;
+            for outcome in &events {
+                render_outcome(outcome);
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
+                match outcome {
+                    Outcome::GameWon { .. } => break 'game,
+                    Outcome::HandWon { .. } | Outcome::BetFolded { .. } => hand_over = true,
+                    _ => {}
+                }
+            }
 
-impl DerefMut for CardList {
-    fn deref_mut(&mut self) -> &mut Vec<CardWithSuit> {
-        &mut self.0
+            if hand_over {
+                break;
+            }
+        }
     }
 }
 
-impl fmt::Display for CardList {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}",
-            self.0
-                .iter()
-                .map(|c| format!("{}", c))
-                .collect::<Vec<_>>()
-                .join(" - ")
-        )
+/// Which opponent to play against, picked via `truco [frequency|ismcts]`
+/// (defaults to `frequency`, the cheaper one).
+fn choose_opponent() -> Opponent {
+    match std::env::args().nth(1).as_deref() {
+        Some("ismcts") => Opponent::Ismcts(IsmctsAi::default()),
+        _ => Opponent::Frequency(FrequencyAi::new()),
     }
 }
 
-struct Game {
-    rng: ThreadRng,
-    player_hand: CardList,
-    computer_hand: CardList,
-    turned_card: Option<CardWithSuit>,
-    deck: CardList,
-    player_score: u8,
-    computer_score: u8,
-    turn: Turn,
-    // Turn score is positive at the end, then player won, negative, computer won
-    turn_score: i8,
-    turn_stack: CardList,
-    score_increment: u8,
-}
-
-impl Game {
-    fn new() -> Game {
-        Game {
-            rng: thread_rng(),
-            player_hand: CardList::new(),
-            computer_hand: CardList::new(),
-            turned_card: None,
-            deck: CardList::new(),
-            player_score: 0,
-            computer_score: 0,
-            turn: Turn::Player,
-            turn_score: 0,
-            turn_stack: CardList::new(),
-            score_increment: 1,
-        }
-    }
-
-    fn init(&mut self) {
-        self.build_deck();
+fn choose_action(game: &GameState, computer: &mut Opponent) -> Action {
+    match game.turn() {
+        Turn::Player => choose_player_action(game),
+        Turn::Computer => computer.choose_action(game),
     }
+}
 
-    fn get_scores(&self) -> (u8, u8) {
-        (self.player_score, self.computer_score)
-    }
+fn choose_player_action(game: &GameState) -> Action {
+    let legal = game.legal_actions();
 
-    fn build_deck(&mut self) {
-        let suits: [Suit; 4] = [Suit::Diamonds, Suit::Spades, Suit::Clubs, Suit::Hearts];
-        let cards: [Card; 10] = [
-            Card::Three,
-            Card::Two,
-            Card::Ace,
-            Card::Knight,
-            Card::Joker,
-            Card::Queen,
-            Card::Seven,
-            Card::Six,
-            Card::Five,
-            Card::Four,
-        ];
+    if legal.contains(&Action::Accept) {
+        println!(
+            "O computador pediu {}! Aceita (a), aumenta (r) ou corre (f)?",
+            game.bet().current_stake()
+        );
 
-        for s in suits.iter() {
-            for c in cards.iter() {
-                self.deck.push(CardWithSuit(*c, *s));
+        loop {
+            match read_choice().as_str() {
+                "a" => return Action::Accept,
+                "r" if legal.contains(&Action::CallTruco) => return Action::CallTruco,
+                "f" => return Action::Fold,
+                _ => println!("Resposta inválida, use a, r ou f."),
             }
         }
-
-        self.deck.shuffle(&mut self.rng);
     }
 
-    fn build_hands_and_flip(&mut self) {
-        for i in 0..6 {
-            let card = self.deck.pop().unwrap();
+    println!("Sua vez! Qual carta vai jogar? {}", game.hand_of(Turn::Player));
 
-            if is_odd(i) && self.turn == Turn::Player {
-                self.computer_hand.push(card);
-            } else {
-                self.player_hand.push(card);
-            }
-        }
-
-        println!("player hand: {}", self.player_hand);
-        println!("computer hand: {}", self.computer_hand);
-
-        self.turned_card = self.deck.pop();
+    if legal.contains(&Action::CallTruco) {
+        println!("(ou digite t para pedir truco)");
     }
 
-    fn take_computer_hand(&mut self) -> CardWithSuit {
-        // for now just taking the last card in hand
-        self.computer_hand.pop().unwrap()
-    }
-
-    fn check_who_won_hand(&mut self, drawn_card: &CardWithSuit) {
-        let Some(last_drawn) = self.turn_stack.last() else {
-            return;
-        };
-
-        let turned = self.turned_card.as_ref().expect("Game not initialized");
+    loop {
+        let input = read_choice();
 
-        let drawn_or_pile: bool;
-
-        if drawn_card.is_manilha(turned) && last_drawn.is_manilha(turned) {
-            drawn_or_pile = last_drawn > drawn_card;
-        } else if drawn_card.is_manilha(turned) {
-            drawn_or_pile = true;
-        } else if last_drawn.is_manilha(turned) {
-            drawn_or_pile = false;
-        } else {
-            drawn_or_pile = last_drawn > drawn_card;
+        if input == "t" && legal.contains(&Action::CallTruco) {
+            return Action::CallTruco;
         }
 
-        let player_won: bool; // true => Player, false => Computer
-
-        if drawn_or_pile {
-            match self.turn {
-                Turn::Player => {
-                    player_won = true;
-                }
-                Turn::Computer => {
-                    player_won = false;
-                }
-            }
-        } else {
-            match self.turn {
-                Turn::Player => {
-                    player_won = false;
-                }
-                Turn::Computer => {
-                    player_won = true;
-                }
+        match input.parse::<usize>() {
+            Ok(n) if n >= 1 && legal.contains(&Action::PlayCard(n - 1)) => {
+                return Action::PlayCard(n - 1);
             }
-        }
-
-        if player_won {
-            self.turn_score += 1;
-        } else {
-            self.turn_score -= 1;
+            _ => println!("Escolha inválida."),
         }
     }
+}
 
-    fn reset_turn(&mut self) {
-        self.build_deck();
-        self.turn_score = 0;
-        self.computer_hand = CardList::new();
-        self.player_hand = CardList::new();
-        self.turned_card = None;
-    }
-
-    fn start() {
-        println!("Iniciando...");
-        println!("Jogando contra o computador...");
-
-        let mut game = Game::new();
-
-        game.init();
-
-        // Main game loop
-        loop {
-            let (ps, cs) = game.get_scores();
-            println!("Placar atual: Jogador: {} - Computador: {}", ps, cs);
-
-            game.build_hands_and_flip();
-
-            println!("Sua mão: {}", game.player_hand);
-
-            if let Some(ref carta_virada) = game.turned_card {
-                println!("Carta virada: {}", carta_virada);
-            }
-
-            // Running the turn
-            for hand_index in 0..6 {
-                println!("A cartas jogadas foram: {}", game.turn_stack);
-
-                let drawn_card = match game.turn {
-                    Turn::Player => {
-                        println!("Sua vez! Qual carta vai jogar? {}", game.player_hand);
-
-                        let chosen_card_index = choose_card();
-                        game.player_hand.swap_remove(chosen_card_index as usize - 1)
-                    }
-                    Turn::Computer => {
-                        let computer_card = game.take_computer_hand();
-                        println!("O computador jogou a carta {}", computer_card);
-                        computer_card
-                    }
-                };
-
-                if hand_index > 0 && is_odd(hand_index) {
-                    game.check_who_won_hand(&drawn_card);
-                    if game.turn_score >= 0 {
-                        println!("Você ganhou essa mão!");
-                    } else {
-                        println!("O computador ganhou essa mão!");
-                    }
-                }
-
-                game.turn_stack.push(drawn_card);
-
-                game.turn = match game.turn {
-                    Turn::Player => Turn::Computer,
-                    Turn::Computer => Turn::Player,
-                };
-            }
-
-            game.reset_turn();
-
-            if game.turn_score >= 0 {
-                game.player_score += game.score_increment;
-            } else {
-                game.computer_score += game.score_increment;
-            }
-
-            if game.player_score >= 12 {
-                println!("Você ganhou! Parabéns!");
-                break;
-            } else if game.computer_score >= 12 {
-                println!("O computador ganhou, mais sorte na próxima vez!");
-                break;
+fn render_outcome(outcome: &Outcome) {
+    match outcome {
+        Outcome::CardPlayed { player, card } => match player {
+            Turn::Player => println!("Você jogou a carta {}", card),
+            Turn::Computer => println!("O computador jogou a carta {}", card),
+        },
+        Outcome::TrickResolved { trick, winner } => {
+            let ordinal = match trick {
+                1 => "primeira",
+                2 => "segunda",
+                _ => "terceira",
+            };
+
+            match winner {
+                TrickWinner::Player => println!("Você ganhou a {}!", ordinal),
+                TrickWinner::Computer => println!("O computador ganhou a {}!", ordinal),
+                TrickWinner::Tied => println!("Empatou a {}!", ordinal),
             }
         }
-    }
-}
-
-fn is_odd(n: u32) -> bool {
-    n % 2 == 1
-}
-
-fn choose_card() -> u8 {
+        Outcome::BetRaised { caller, stake } => match caller {
+            Turn::Player => println!("Você pediu {}!", stake),
+            Turn::Computer => println!("O computador pediu {}!", stake),
+        },
+        Outcome::BetAccepted { stake } => {
+            println!("Aceito! A mão vale {} pontos.", stake.points());
+        }
+        Outcome::BetFolded { winner, points } => match winner {
+            Turn::Player => println!("O computador correu. Você leva {} pontos.", points),
+            Turn::Computer => println!("Você correu. O computador leva {} pontos.", points),
+        },
+        Outcome::HandWon { winner, points } => match winner {
+            Turn::Player => println!("Você ganhou essa mão! (+{} pontos)", points),
+            Turn::Computer => println!("O computador ganhou essa mão! (+{} pontos)", points),
+        },
+        Outcome::GameWon { winner } => match winner {
+            Turn::Player => println!("Você ganhou! Parabéns!"),
+            Turn::Computer => println!("O computador ganhou, mais sorte na próxima vez!"),
+        },
+    }
+}
+
+fn read_choice() -> String {
     let mut input = String::new();
     std::io::stdin()
         .read_line(&mut input)
         .expect("Failed to read line");
-    input.trim().parse().expect("input is not integer")
-}
-
-fn main() {
-    println!("Bora jogar um truco?");
-
-    Game::start()
+    input.trim().to_lowercase()
 }
@@ -0,0 +1,143 @@
+//! Best-of-three trick resolution, including truco's "empate" (tie) rules.
+
+use crate::card::Turn;
+
+/// The outcome of comparing the two cards played in a single trick.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TrickWinner {
+    Player,
+    Computer,
+    Tied,
+}
+
+impl From<Turn> for TrickWinner {
+    fn from(turn: Turn) -> Self {
+        match turn {
+            Turn::Player => TrickWinner::Player,
+            Turn::Computer => TrickWinner::Computer,
+        }
+    }
+}
+
+/// Tracks the three tricks of a hand and decides who wins it.
+///
+/// The rules: the first side to win two tricks wins the hand; a tie in the
+/// first trick hands the hand to the winner of the second; a tie in the
+/// second trick (after the first was won outright) leaves the hand with the
+/// first trick's winner; a tie in the first two tricks, or a 1-1 split,
+/// defers to the third trick; and if every trick ties, the hand goes to
+/// whoever is "mão" (the hand leader).
+#[derive(Clone)]
+pub struct RoundResolver {
+    tricks: Vec<TrickWinner>,
+    mao: Turn,
+}
+
+impl RoundResolver {
+    pub fn new(mao: Turn) -> RoundResolver {
+        RoundResolver {
+            tricks: Vec::with_capacity(3),
+            mao,
+        }
+    }
+
+    pub fn history(&self) -> &[TrickWinner] {
+        &self.tricks
+    }
+
+    /// Records the winner of the trick just played. Returns the hand's
+    /// winner once the outcome is decided, which may happen before all
+    /// three tricks are played.
+    pub fn record_trick(&mut self, winner: TrickWinner) -> Option<Turn> {
+        self.tricks.push(winner);
+        self.resolve()
+    }
+
+    fn resolve(&self) -> Option<Turn> {
+        if self.tricks.len() < 2 {
+            return None;
+        }
+
+        let first = self.tricks[0];
+        let second = self.tricks[1];
+
+        match (first, second) {
+            (TrickWinner::Player, TrickWinner::Player) => return Some(Turn::Player),
+            (TrickWinner::Computer, TrickWinner::Computer) => return Some(Turn::Computer),
+            (TrickWinner::Tied, TrickWinner::Player) => return Some(Turn::Player),
+            (TrickWinner::Tied, TrickWinner::Computer) => return Some(Turn::Computer),
+            (TrickWinner::Player, TrickWinner::Tied) => return Some(Turn::Player),
+            (TrickWinner::Computer, TrickWinner::Tied) => return Some(Turn::Computer),
+            // Either both tricks tied, or a 1-1 split: the third trick decides.
+            _ => {}
+        }
+
+        let third = *self.tricks.get(2)?;
+
+        match third {
+            TrickWinner::Player => Some(Turn::Player),
+            TrickWinner::Computer => Some(Turn::Computer),
+            TrickWinner::Tied => match first {
+                TrickWinner::Player => Some(Turn::Player),
+                TrickWinner::Computer => Some(Turn::Computer),
+                TrickWinner::Tied => Some(self.mao),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fewer_than_two_tricks_is_undecided() {
+        let mut resolver = RoundResolver::new(Turn::Player);
+        assert_eq!(resolver.record_trick(TrickWinner::Player), None);
+    }
+
+    #[test]
+    fn winning_first_two_tricks_outright_decides_the_hand() {
+        let mut resolver = RoundResolver::new(Turn::Player);
+        resolver.record_trick(TrickWinner::Computer);
+        assert_eq!(resolver.record_trick(TrickWinner::Computer), Some(Turn::Computer));
+    }
+
+    #[test]
+    fn a_tied_first_trick_hands_the_hand_to_the_second_trick_winner() {
+        let mut resolver = RoundResolver::new(Turn::Computer);
+        resolver.record_trick(TrickWinner::Tied);
+        assert_eq!(resolver.record_trick(TrickWinner::Player), Some(Turn::Player));
+    }
+
+    #[test]
+    fn a_tied_second_trick_leaves_the_hand_with_the_first_tricks_winner() {
+        let mut resolver = RoundResolver::new(Turn::Computer);
+        resolver.record_trick(TrickWinner::Player);
+        assert_eq!(resolver.record_trick(TrickWinner::Tied), Some(Turn::Player));
+    }
+
+    #[test]
+    fn a_one_one_split_defers_to_the_third_trick() {
+        let mut resolver = RoundResolver::new(Turn::Player);
+        resolver.record_trick(TrickWinner::Player);
+        assert_eq!(resolver.record_trick(TrickWinner::Computer), None);
+        assert_eq!(resolver.record_trick(TrickWinner::Computer), Some(Turn::Computer));
+    }
+
+    #[test]
+    fn a_tied_third_trick_after_a_one_one_split_falls_back_to_the_first_tricks_winner() {
+        let mut resolver = RoundResolver::new(Turn::Computer);
+        resolver.record_trick(TrickWinner::Player);
+        resolver.record_trick(TrickWinner::Computer);
+        assert_eq!(resolver.record_trick(TrickWinner::Tied), Some(Turn::Player));
+    }
+
+    #[test]
+    fn an_all_tied_hand_goes_to_mao() {
+        let mut resolver = RoundResolver::new(Turn::Computer);
+        resolver.record_trick(TrickWinner::Tied);
+        assert_eq!(resolver.record_trick(TrickWinner::Tied), None);
+        assert_eq!(resolver.record_trick(TrickWinner::Tied), Some(Turn::Computer));
+    }
+}